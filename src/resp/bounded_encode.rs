@@ -0,0 +1,397 @@
+//! Bounded, allocation-free encoding for constrained targets.
+//!
+//! Enabled by the `bounded-encode` feature. Mirrors [`RespEncode`] but
+//! writes into a const-generic [`heapless::Vec`] instead of a growable
+//! `Vec`, so encoding a frame never allocates and can never exceed `N`
+//! bytes.
+//!
+//! This is an alternative, no-alloc encode path for every [`RespFrame`]
+//! variant — it does not make the crate buildable on a real
+//! `#![no_std]` target. The rest of the crate (`std::io::Read`,
+//! `BTreeMap`-backed containers, `std::io::Error` conversions) still
+//! depends on `std` unconditionally regardless of this feature.
+
+use heapless::Vec as HVec;
+
+use super::{
+    BigNumber, BulkString, RespArray, RespAttribute, RespError, RespFrame, RespMap, RespNull,
+    RespNullArray, RespNullBulkString, RespPush, RespSet, RespVerbatimString, SimpleError,
+    SimpleString,
+};
+
+/// Error returned when a frame does not fit into the caller's
+/// fixed-size buffer.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CapacityError;
+
+impl From<CapacityError> for RespError {
+    fn from(_: CapacityError) -> Self {
+        RespError::FrameTooLarge(usize::MAX)
+    }
+}
+
+pub trait BoundedEncode {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError>;
+}
+
+impl BoundedEncode for SimpleString {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.push(b'+').map_err(|_| CapacityError)?;
+        buf.extend_from_slice(self.as_bytes())
+            .map_err(|_| CapacityError)?;
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)
+    }
+}
+
+impl BoundedEncode for SimpleError {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.push(b'-').map_err(|_| CapacityError)?;
+        buf.extend_from_slice(self.as_bytes())
+            .map_err(|_| CapacityError)?;
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)
+    }
+}
+
+impl BoundedEncode for BulkString {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.push(b'$').map_err(|_| CapacityError)?;
+        buf.extend_from_slice(&itoa(self.len())).map_err(|_| CapacityError)?;
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)?;
+        buf.extend_from_slice(&self).map_err(|_| CapacityError)?;
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)
+    }
+}
+
+impl BoundedEncode for RespNullBulkString {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.extend_from_slice(b"$-1\r\n").map_err(|_| CapacityError)
+    }
+}
+
+impl BoundedEncode for i64 {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        use core::fmt::Write;
+        buf.push(b':').map_err(|_| CapacityError)?;
+        let sign = if self < 0 { "" } else { "+" };
+        write!(Writer(buf), "{}{}", sign, self).map_err(|_| CapacityError)?;
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)
+    }
+}
+
+impl BoundedEncode for bool {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.extend_from_slice(if self { b"#t\r\n" } else { b"#f\r\n" })
+            .map_err(|_| CapacityError)
+    }
+}
+
+impl BoundedEncode for f64 {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        use core::fmt::Write;
+        buf.push(b',').map_err(|_| CapacityError)?;
+        if self.abs() > 1e+8 || self.abs() < 1e-8 {
+            write!(Writer(buf), "{:+e}", self).map_err(|_| CapacityError)?;
+        } else {
+            let sign = if self < 0.0 { "" } else { "+" };
+            write!(Writer(buf), "{}{}", sign, self).map_err(|_| CapacityError)?;
+        }
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)
+    }
+}
+
+impl BoundedEncode for RespNull {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.extend_from_slice(b"_\r\n").map_err(|_| CapacityError)
+    }
+}
+
+impl BoundedEncode for RespNullArray {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.extend_from_slice(b"*-1\r\n").map_err(|_| CapacityError)
+    }
+}
+
+impl BoundedEncode for BigNumber {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.push(b'(').map_err(|_| CapacityError)?;
+        buf.extend_from_slice(self.as_bytes())
+            .map_err(|_| CapacityError)?;
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)
+    }
+}
+
+impl BoundedEncode for RespVerbatimString {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.push(b'=').map_err(|_| CapacityError)?;
+        buf.extend_from_slice(&itoa(self.data.len() + 4))
+            .map_err(|_| CapacityError)?;
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)?;
+        buf.extend_from_slice(&self.format).map_err(|_| CapacityError)?;
+        buf.push(b':').map_err(|_| CapacityError)?;
+        buf.extend_from_slice(&self.data).map_err(|_| CapacityError)?;
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)
+    }
+}
+
+impl BoundedEncode for RespArray {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.push(b'*').map_err(|_| CapacityError)?;
+        buf.extend_from_slice(&itoa(self.0.len()))
+            .map_err(|_| CapacityError)?;
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)?;
+        for item in self.0 {
+            item.encode_into(buf)?;
+        }
+        Ok(())
+    }
+}
+
+impl BoundedEncode for RespSet {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.push(b'~').map_err(|_| CapacityError)?;
+        buf.extend_from_slice(&itoa(self.0.len()))
+            .map_err(|_| CapacityError)?;
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)?;
+        for item in self.0 {
+            item.encode_into(buf)?;
+        }
+        Ok(())
+    }
+}
+
+impl BoundedEncode for RespPush {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.push(b'>').map_err(|_| CapacityError)?;
+        buf.extend_from_slice(&itoa(self.0.len()))
+            .map_err(|_| CapacityError)?;
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)?;
+        for item in self.0 {
+            item.encode_into(buf)?;
+        }
+        Ok(())
+    }
+}
+
+impl BoundedEncode for RespMap {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.push(b'%').map_err(|_| CapacityError)?;
+        buf.extend_from_slice(&itoa(self.0.len()))
+            .map_err(|_| CapacityError)?;
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)?;
+        for (key, value) in self.0 {
+            SimpleString::new(key).encode_into(buf)?;
+            value.encode_into(buf)?;
+        }
+        Ok(())
+    }
+}
+
+impl BoundedEncode for RespAttribute {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        buf.push(b'|').map_err(|_| CapacityError)?;
+        buf.extend_from_slice(&itoa(self.0.len()))
+            .map_err(|_| CapacityError)?;
+        buf.extend_from_slice(b"\r\n").map_err(|_| CapacityError)?;
+        for (key, value) in self.0 {
+            SimpleString::new(key).encode_into(buf)?;
+            value.encode_into(buf)?;
+        }
+        Ok(())
+    }
+}
+
+impl BoundedEncode for RespFrame {
+    fn encode_into<const N: usize>(self, buf: &mut HVec<u8, N>) -> Result<(), CapacityError> {
+        match self {
+            RespFrame::SimpleString(v) => v.encode_into(buf),
+            RespFrame::Error(v) => v.encode_into(buf),
+            RespFrame::Integer(v) => v.encode_into(buf),
+            RespFrame::BulkString(v) => v.encode_into(buf),
+            RespFrame::NullBulkString(v) => v.encode_into(buf),
+            RespFrame::Array(v) => v.encode_into(buf),
+            RespFrame::Null(v) => v.encode_into(buf),
+            RespFrame::NullArray(v) => v.encode_into(buf),
+            RespFrame::Boolean(v) => v.encode_into(buf),
+            RespFrame::Double(v) => v.encode_into(buf),
+            RespFrame::Map(v) => v.encode_into(buf),
+            RespFrame::Set(v) => v.encode_into(buf),
+            RespFrame::BigNumber(v) => v.encode_into(buf),
+            RespFrame::VerbatimString(v) => v.encode_into(buf),
+            RespFrame::Push(v) => v.encode_into(buf),
+            RespFrame::Attribute(v) => v.encode_into(buf),
+        }
+    }
+}
+
+/// Adapts a [`HVec`] into a [`core::fmt::Write`] sink, for formatting
+/// signed integers and floats without allocating a `String`.
+struct Writer<'a, const N: usize>(&'a mut HVec<u8, N>);
+
+impl<const N: usize> core::fmt::Write for Writer<'_, N> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0.extend_from_slice(s.as_bytes()).map_err(|_| core::fmt::Error)
+    }
+}
+
+/// Formats `n` in decimal without allocating, for use in contexts
+/// where `format!` (which allocates a `String`) is unavailable.
+fn itoa(n: usize) -> heapless::Vec<u8, 20> {
+    let mut digits: heapless::Vec<u8, 20> = heapless::Vec::new();
+    let mut n = n;
+    if n == 0 {
+        digits.push(b'0').ok();
+    }
+    while n > 0 {
+        digits.push(b'0' + (n % 10) as u8).ok();
+        n /= 10;
+    }
+    let mut out: heapless::Vec<u8, 20> = heapless::Vec::new();
+    for &d in digits.iter().rev() {
+        out.push(d).ok();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode<T: BoundedEncode, const N: usize>(value: T) -> HVec<u8, N> {
+        let mut buf = HVec::new();
+        value.encode_into(&mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_encode_simple_string() {
+        let buf: HVec<u8, 32> = encode(SimpleString::new("OK"));
+        assert_eq!(&buf[..], b"+OK\r\n");
+    }
+
+    #[test]
+    fn test_encode_simple_error() {
+        let buf: HVec<u8, 32> = encode(SimpleError::new("ERR"));
+        assert_eq!(&buf[..], b"-ERR\r\n");
+    }
+
+    #[test]
+    fn test_encode_integer() {
+        let buf: HVec<u8, 32> = encode(42i64);
+        assert_eq!(&buf[..], b":+42\r\n");
+    }
+
+    #[test]
+    fn test_encode_bulk_string() {
+        let buf: HVec<u8, 32> = encode(BulkString::new(b"Hello, World!".to_vec()));
+        assert_eq!(&buf[..], b"$13\r\nHello, World!\r\n");
+    }
+
+    #[test]
+    fn test_encode_null_bulk_string() {
+        let buf: HVec<u8, 32> = encode(RespNullBulkString);
+        assert_eq!(&buf[..], b"$-1\r\n");
+    }
+
+    #[test]
+    fn test_encode_null() {
+        let buf: HVec<u8, 32> = encode(RespNull);
+        assert_eq!(&buf[..], b"_\r\n");
+    }
+
+    #[test]
+    fn test_encode_null_array() {
+        let buf: HVec<u8, 32> = encode(RespNullArray);
+        assert_eq!(&buf[..], b"*-1\r\n");
+    }
+
+    #[test]
+    fn test_encode_bool() {
+        let buf: HVec<u8, 32> = encode(true);
+        assert_eq!(&buf[..], b"#t\r\n");
+
+        let buf: HVec<u8, 32> = encode(false);
+        assert_eq!(&buf[..], b"#f\r\n");
+    }
+
+    #[test]
+    fn test_encode_double() {
+        let buf: HVec<u8, 32> = encode(1.5f64);
+        assert_eq!(&buf[..], b",+1.5\r\n");
+
+        let buf: HVec<u8, 32> = encode(1.23456e+8_f64);
+        assert_eq!(&buf[..], b",+1.23456e8\r\n");
+    }
+
+    #[test]
+    fn test_encode_big_number() {
+        let buf: HVec<u8, 64> = encode(BigNumber::new("3492890328409238509324850943850943825024385"));
+        assert_eq!(&buf[..], b"(3492890328409238509324850943850943825024385\r\n");
+    }
+
+    #[test]
+    fn test_encode_verbatim_string() {
+        let buf: HVec<u8, 32> = encode(RespVerbatimString::new(*b"txt", "Some string".as_bytes()));
+        assert_eq!(&buf[..], b"=15\r\ntxt:Some string\r\n");
+    }
+
+    #[test]
+    fn test_encode_array() {
+        let buf: HVec<u8, 64> = encode(RespArray::new([
+            SimpleString::new("set").into(),
+            BulkString::new("hello").into(),
+        ]));
+        assert_eq!(&buf[..], b"*2\r\n+set\r\n$5\r\nhello\r\n");
+    }
+
+    #[test]
+    fn test_encode_set() {
+        let buf: HVec<u8, 64> = encode(RespSet::new([
+            1234.into(),
+            BulkString::new("world").into(),
+        ]));
+        assert_eq!(&buf[..], b"~2\r\n:+1234\r\n$5\r\nworld\r\n");
+    }
+
+    #[test]
+    fn test_encode_push() {
+        let buf: HVec<u8, 64> = encode(RespPush::new([
+            SimpleString::new("pubsub").into(),
+            BulkString::new("message").into(),
+        ]));
+        assert_eq!(&buf[..], b">2\r\n+pubsub\r\n$7\r\nmessage\r\n");
+    }
+
+    #[test]
+    fn test_encode_map() {
+        let mut map = RespMap::new();
+        map.insert("hello".to_string(), BulkString::new("world").into());
+
+        let buf: HVec<u8, 64> = encode(map);
+        assert_eq!(&buf[..], b"%1\r\n+hello\r\n$5\r\nworld\r\n");
+    }
+
+    #[test]
+    fn test_encode_attribute() {
+        let mut attr = RespAttribute::new();
+        attr.insert("key-popularity".to_string(), BulkString::new("a").into());
+
+        let buf: HVec<u8, 64> = encode(attr);
+        assert_eq!(&buf[..], b"|1\r\n+key-popularity\r\n$1\r\na\r\n");
+    }
+
+    #[test]
+    fn test_encode_into_via_resp_frame() {
+        let frame: RespFrame = SimpleString::new("OK").into();
+        let buf: HVec<u8, 32> = encode(frame);
+        assert_eq!(&buf[..], b"+OK\r\n");
+    }
+
+    #[test]
+    fn test_encode_into_returns_capacity_error_instead_of_panicking() {
+        let mut buf: HVec<u8, 4> = HVec::new();
+        assert_eq!(
+            SimpleString::new("too long for this buffer").encode_into(&mut buf),
+            Err(CapacityError)
+        );
+    }
+}
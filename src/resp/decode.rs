@@ -0,0 +1,822 @@
+use std::io::Read;
+
+use bytes::BytesMut;
+
+use super::{
+    calc_total_length, extract_simple_frame_data, find_crlf, input::materialize_upto,
+    parse_length_checked, BigNumber, BulkString, DecodeLimits, Input, RespArray, RespAttribute,
+    RespDecode, RespError, RespFrame, RespMap, RespNull, RespNullArray, RespNullBulkString,
+    RespPush, RespSet, RespVerbatimString, SimpleError, SimpleString, CRLF_LEN,
+};
+
+const READ_CHUNK: usize = 4096;
+
+/// Starting size of the header probe `expect_length_bounded` materializes,
+/// before growing it.
+///
+/// Large enough to resolve any of the fixed-prefix frames (simple
+/// strings, integers, booleans, ...) and most bulk-string/array headers
+/// in one shot, small enough that decoding a tiny frame out of a large
+/// buffered backlog (e.g. a burst of pipelined commands) doesn't copy
+/// the whole backlog just to read the next frame's own header.
+const INITIAL_PROBE_LEN: usize = 64;
+
+/// Computes `T::expect_length` against a growing prefix of `input`'s
+/// buffered bytes instead of materializing everything available.
+///
+/// Starts at [`INITIAL_PROBE_LEN`] bytes (or `available`, if smaller) and
+/// doubles the probe on a `NotComplete` caused only by the probe window
+/// being too small to see the whole header — not a real read, just a
+/// wider look at bytes already sitting in `input`'s buffer — until
+/// `expect_length` succeeds, fails for a real reason, or the probe has
+/// grown to cover everything currently available.
+fn expect_length_bounded<T: RespDecode>(
+    input: &impl Input,
+    available: usize,
+    limits: DecodeLimits,
+    depth: usize,
+) -> Result<usize, RespError> {
+    let mut probe = INITIAL_PROBE_LEN.min(available);
+    loop {
+        let snapshot = materialize_upto(input, available, probe);
+        match T::expect_length(&snapshot, limits, depth) {
+            Err(RespError::NotComplete) if probe < available => {
+                probe = (probe * 2).min(available);
+            }
+            other => return other,
+        }
+    }
+}
+
+impl RespDecode for SimpleString {
+    const PREFIX: &'static str = "+";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        let end = total - CRLF_LEN;
+        Ok(SimpleString::new(
+            String::from_utf8_lossy(&raw[Self::PREFIX.len()..end]).into_owned(),
+        ))
+    }
+
+    fn expect_length(buf: &[u8], _limits: DecodeLimits, _depth: usize) -> Result<usize, RespError> {
+        let end = extract_simple_frame_data(buf, Self::PREFIX)?;
+        Ok(end + CRLF_LEN)
+    }
+}
+
+impl RespDecode for SimpleError {
+    const PREFIX: &'static str = "-";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        let end = total - CRLF_LEN;
+        Ok(SimpleError::new(
+            String::from_utf8_lossy(&raw[Self::PREFIX.len()..end]).into_owned(),
+        ))
+    }
+
+    fn expect_length(buf: &[u8], _limits: DecodeLimits, _depth: usize) -> Result<usize, RespError> {
+        let end = extract_simple_frame_data(buf, Self::PREFIX)?;
+        Ok(end + CRLF_LEN)
+    }
+}
+
+impl RespDecode for i64 {
+    const PREFIX: &'static str = ":";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        let end = total - CRLF_LEN;
+        Ok(String::from_utf8_lossy(&raw[Self::PREFIX.len()..end]).parse()?)
+    }
+
+    fn expect_length(buf: &[u8], _limits: DecodeLimits, _depth: usize) -> Result<usize, RespError> {
+        let end = extract_simple_frame_data(buf, Self::PREFIX)?;
+        Ok(end + CRLF_LEN)
+    }
+}
+
+impl RespDecode for bool {
+    const PREFIX: &'static str = "#";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        let end = total - CRLF_LEN;
+        match &raw[Self::PREFIX.len()..end] {
+            b"t" => Ok(true),
+            b"f" => Ok(false),
+            other => Err(RespError::InvalidFrame(format!(
+                "invalid boolean: {:?}",
+                other
+            ))),
+        }
+    }
+
+    fn expect_length(buf: &[u8], _limits: DecodeLimits, _depth: usize) -> Result<usize, RespError> {
+        let end = extract_simple_frame_data(buf, Self::PREFIX)?;
+        Ok(end + CRLF_LEN)
+    }
+}
+
+impl RespDecode for f64 {
+    const PREFIX: &'static str = ",";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        let end = total - CRLF_LEN;
+        Ok(String::from_utf8_lossy(&raw[Self::PREFIX.len()..end]).parse()?)
+    }
+
+    fn expect_length(buf: &[u8], _limits: DecodeLimits, _depth: usize) -> Result<usize, RespError> {
+        let end = extract_simple_frame_data(buf, Self::PREFIX)?;
+        Ok(end + CRLF_LEN)
+    }
+}
+
+impl RespDecode for RespNull {
+    const PREFIX: &'static str = "_";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        Ok(RespNull)
+    }
+
+    fn expect_length(buf: &[u8], _limits: DecodeLimits, _depth: usize) -> Result<usize, RespError> {
+        let end = find_crlf(buf, 1).ok_or(RespError::NotComplete)?;
+        Ok(end + CRLF_LEN)
+    }
+}
+
+impl RespDecode for RespNullBulkString {
+    const PREFIX: &'static str = "$";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        Ok(RespNullBulkString)
+    }
+
+    fn expect_length(buf: &[u8], _limits: DecodeLimits, _depth: usize) -> Result<usize, RespError> {
+        if buf.starts_with(b"$-1\r\n") {
+            Ok(5)
+        } else {
+            Err(RespError::InvalidFrameType(
+                "expected a null bulk string ($-1\\r\\n)".to_string(),
+            ))
+        }
+    }
+}
+
+impl RespDecode for RespNullArray {
+    const PREFIX: &'static str = "*";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        Ok(RespNullArray)
+    }
+
+    fn expect_length(buf: &[u8], _limits: DecodeLimits, _depth: usize) -> Result<usize, RespError> {
+        if buf.starts_with(b"*-1\r\n") {
+            Ok(5)
+        } else {
+            Err(RespError::InvalidFrameType(
+                "expected a null array (*-1\\r\\n)".to_string(),
+            ))
+        }
+    }
+}
+
+impl RespDecode for BulkString {
+    const PREFIX: &'static str = "$";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        let (end, len) = parse_length_checked(&raw, Self::PREFIX, limits)?;
+        let data = raw[end + CRLF_LEN..end + CRLF_LEN + len].to_vec();
+        Ok(BulkString::new(data))
+    }
+
+    fn expect_length(buf: &[u8], limits: DecodeLimits, _depth: usize) -> Result<usize, RespError> {
+        let (end, len) = parse_length_checked(buf, Self::PREFIX, limits)?;
+        Ok(end + CRLF_LEN + len + CRLF_LEN)
+    }
+}
+
+impl RespDecode for RespArray {
+    const PREFIX: &'static str = "*";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        let (end, len) = parse_length_checked(&raw, Self::PREFIX, limits)?;
+        let mut body = BytesMut::from(&raw[end + CRLF_LEN..]);
+
+        let mut frames = Vec::with_capacity(len);
+        for _ in 0..len {
+            frames.push(RespFrame::decode(&mut body, limits, depth + 1)?);
+        }
+        Ok(RespArray::new(frames))
+    }
+
+    fn expect_length(buf: &[u8], limits: DecodeLimits, depth: usize) -> Result<usize, RespError> {
+        let (end, len) = parse_length_checked(buf, Self::PREFIX, limits)?;
+        calc_total_length(buf, end, len, Self::PREFIX, limits, depth)
+    }
+}
+
+impl RespDecode for RespSet {
+    const PREFIX: &'static str = "~";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        let (end, len) = parse_length_checked(&raw, Self::PREFIX, limits)?;
+        let mut body = BytesMut::from(&raw[end + CRLF_LEN..]);
+
+        let mut frames = Vec::with_capacity(len);
+        for _ in 0..len {
+            frames.push(RespFrame::decode(&mut body, limits, depth + 1)?);
+        }
+        Ok(RespSet::new(frames))
+    }
+
+    fn expect_length(buf: &[u8], limits: DecodeLimits, depth: usize) -> Result<usize, RespError> {
+        let (end, len) = parse_length_checked(buf, Self::PREFIX, limits)?;
+        calc_total_length(buf, end, len, Self::PREFIX, limits, depth)
+    }
+}
+
+impl RespDecode for RespMap {
+    const PREFIX: &'static str = "%";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        let (end, len) = parse_length_checked(&raw, Self::PREFIX, limits)?;
+        let mut body = BytesMut::from(&raw[end + CRLF_LEN..]);
+
+        let mut map = RespMap::new();
+        for _ in 0..len {
+            let key = SimpleString::decode(&mut body, limits, depth + 1)?;
+            let value = RespFrame::decode(&mut body, limits, depth + 1)?;
+            map.insert((*key).clone(), value);
+        }
+        Ok(map)
+    }
+
+    fn expect_length(buf: &[u8], limits: DecodeLimits, depth: usize) -> Result<usize, RespError> {
+        let (end, len) = parse_length_checked(buf, Self::PREFIX, limits)?;
+        calc_total_length(buf, end, len, Self::PREFIX, limits, depth)
+    }
+}
+
+/// Dispatches on the frame's leading prefix byte, mirroring
+/// [`super::decode_ref`]'s borrowed equivalent but producing owned
+/// variants.
+impl RespDecode for RespFrame {
+    const PREFIX: &'static str = "";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        // Only the prefix byte (and, for `$`/`*`, enough to rule out the
+        // null-frame literals) drives the dispatch below — no need to
+        // materialize the whole buffered backlog just to read it.
+        let available = input.remaining();
+        let snapshot = materialize_upto(&*input, available, 5);
+        match snapshot.first().ok_or(RespError::NotComplete)? {
+            b'+' => Ok(SimpleString::decode(input, limits, depth)?.into()),
+            b'-' => Ok(SimpleError::decode(input, limits, depth)?.into()),
+            b':' => Ok(i64::decode(input, limits, depth)?.into()),
+            b'#' => Ok(bool::decode(input, limits, depth)?.into()),
+            b',' => Ok(f64::decode(input, limits, depth)?.into()),
+            b'_' => Ok(RespNull::decode(input, limits, depth)?.into()),
+            b'$' if snapshot.starts_with(b"$-1\r\n") => {
+                Ok(RespNullBulkString::decode(input, limits, depth)?.into())
+            }
+            b'$' => Ok(BulkString::decode(input, limits, depth)?.into()),
+            b'*' if snapshot.starts_with(b"*-1\r\n") => {
+                Ok(RespNullArray::decode(input, limits, depth)?.into())
+            }
+            b'*' => Ok(RespArray::decode(input, limits, depth)?.into()),
+            b'~' => Ok(RespSet::decode(input, limits, depth)?.into()),
+            b'%' => Ok(RespMap::decode(input, limits, depth)?.into()),
+            b'(' => Ok(BigNumber::decode(input, limits, depth)?.into()),
+            b'=' => Ok(RespVerbatimString::decode(input, limits, depth)?.into()),
+            b'>' => Ok(RespPush::decode(input, limits, depth)?.into()),
+            b'|' => Ok(RespAttribute::decode(input, limits, depth)?.into()),
+            other => Err(RespError::InvalidFrameType(format!(
+                "unsupported prefix: {:?}",
+                *other as char
+            ))),
+        }
+    }
+
+    fn expect_length(buf: &[u8], limits: DecodeLimits, depth: usize) -> Result<usize, RespError> {
+        match buf.first().ok_or(RespError::NotComplete)? {
+            b'+' => SimpleString::expect_length(buf, limits, depth),
+            b'-' => SimpleError::expect_length(buf, limits, depth),
+            b':' => i64::expect_length(buf, limits, depth),
+            b'#' => bool::expect_length(buf, limits, depth),
+            b',' => f64::expect_length(buf, limits, depth),
+            b'_' => RespNull::expect_length(buf, limits, depth),
+            b'$' if buf.starts_with(b"$-1\r\n") => {
+                RespNullBulkString::expect_length(buf, limits, depth)
+            }
+            b'$' => BulkString::expect_length(buf, limits, depth),
+            b'*' if buf.starts_with(b"*-1\r\n") => RespNullArray::expect_length(buf, limits, depth),
+            b'*' => RespArray::expect_length(buf, limits, depth),
+            b'~' => RespSet::expect_length(buf, limits, depth),
+            b'%' => RespMap::expect_length(buf, limits, depth),
+            b'(' => BigNumber::expect_length(buf, limits, depth),
+            b'=' => RespVerbatimString::expect_length(buf, limits, depth),
+            b'>' => RespPush::expect_length(buf, limits, depth),
+            b'|' => RespAttribute::expect_length(buf, limits, depth),
+            other => Err(RespError::InvalidFrameType(format!(
+                "unsupported prefix: {:?}",
+                *other as char
+            ))),
+        }
+    }
+}
+
+impl RespDecode for BigNumber {
+    const PREFIX: &'static str = "(";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        let s = String::from_utf8_lossy(&raw[Self::PREFIX.len()..total - CRLF_LEN]);
+        if !is_big_number(&s) {
+            return Err(RespError::InvalidFrame(format!(
+                "invalid big number: {:?}",
+                s
+            )));
+        }
+        Ok(BigNumber::new(s.into_owned()))
+    }
+
+    fn expect_length(buf: &[u8], _limits: DecodeLimits, _depth: usize) -> Result<usize, RespError> {
+        let end = super::extract_simple_frame_data(buf, Self::PREFIX)?;
+        Ok(end + CRLF_LEN)
+    }
+}
+
+/// Whether `s` matches the RESP3 big-number grammar (`-?[0-9]+`).
+pub(super) fn is_big_number(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+impl RespDecode for RespVerbatimString {
+    const PREFIX: &'static str = "=";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        let (end, len) = parse_length_checked(&raw, Self::PREFIX, limits)?;
+        let body = &raw[end + CRLF_LEN..end + CRLF_LEN + len];
+        if body.len() < 4 {
+            return Err(RespError::InvalidFrame(
+                "verbatim string format tag must be 3 bytes".to_string(),
+            ));
+        }
+        let format: [u8; 3] = body[..3].try_into().map_err(|_| {
+            RespError::InvalidFrame("verbatim string format tag must be 3 bytes".to_string())
+        })?;
+        let data = body[4..].to_vec();
+        Ok(RespVerbatimString::new(format, data))
+    }
+
+    fn expect_length(buf: &[u8], limits: DecodeLimits, _depth: usize) -> Result<usize, RespError> {
+        let (end, len) = parse_length_checked(buf, Self::PREFIX, limits)?;
+        Ok(end + CRLF_LEN + len + CRLF_LEN)
+    }
+}
+
+impl RespDecode for RespPush {
+    const PREFIX: &'static str = ">";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        let (end, len) = parse_length_checked(&raw, Self::PREFIX, limits)?;
+        let mut body = BytesMut::from(&raw[end + CRLF_LEN..]);
+
+        let mut frames = Vec::with_capacity(len);
+        for _ in 0..len {
+            frames.push(RespFrame::decode(&mut body, limits, depth + 1)?);
+        }
+        Ok(RespPush::new(frames))
+    }
+
+    fn expect_length(buf: &[u8], limits: DecodeLimits, depth: usize) -> Result<usize, RespError> {
+        let (end, len) = parse_length_checked(buf, Self::PREFIX, limits)?;
+        calc_total_length(buf, end, len, Self::PREFIX, limits, depth)
+    }
+}
+
+impl RespDecode for RespAttribute {
+    const PREFIX: &'static str = "|";
+
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError> {
+        let available = input.remaining();
+        let total = expect_length_bounded::<Self>(&*input, available, limits, depth)?;
+        if available < total {
+            return Err(RespError::NotComplete);
+        }
+
+        let mut raw = vec![0u8; total];
+        input.read(&mut raw);
+        let (end, len) = parse_length_checked(&raw, Self::PREFIX, limits)?;
+        let mut body = BytesMut::from(&raw[end + CRLF_LEN..]);
+
+        let mut attr = RespAttribute::new();
+        for _ in 0..len {
+            let key = SimpleString::decode(&mut body, limits, depth + 1)?;
+            let value = RespFrame::decode(&mut body, limits, depth + 1)?;
+            attr.insert((*key).clone(), value);
+        }
+        Ok(attr)
+    }
+
+    fn expect_length(buf: &[u8], limits: DecodeLimits, depth: usize) -> Result<usize, RespError> {
+        let (end, len) = parse_length_checked(buf, Self::PREFIX, limits)?;
+        calc_total_length(buf, end, len, Self::PREFIX, limits, depth)
+    }
+}
+
+/// Streaming RESP decoder over any [`Read`] source.
+///
+/// Wraps a reader with a growable internal buffer so callers can pull
+/// complete [`RespFrame`]s one at a time without managing their own
+/// re-read loop. Internally it keeps re-using [`RespDecode::decode`]
+/// against the buffered bytes, topping the buffer up from the reader
+/// whenever a frame is not yet complete.
+pub struct Decoder<R> {
+    reader: R,
+    buf: BytesMut,
+    limits: DecodeLimits,
+}
+
+impl<R: Read> Decoder<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_limits(reader, DecodeLimits::default())
+    }
+
+    /// Like [`Decoder::new`], but with caller-supplied bounds on frame
+    /// length and nesting depth instead of [`DecodeLimits::default`].
+    pub fn with_limits(reader: R, limits: DecodeLimits) -> Self {
+        Self {
+            reader,
+            buf: BytesMut::new(),
+            limits,
+        }
+    }
+
+    /// Decode and return the next frame, reading from the underlying
+    /// reader as needed.
+    ///
+    /// Returns `RespError::Eof` if the reader is exhausted cleanly
+    /// between frames (nothing buffered), or `RespError::UnexpectedEof`
+    /// if it is exhausted in the middle of a frame that was already
+    /// partially received.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<RespFrame, RespError> {
+        loop {
+            match RespFrame::decode(&mut self.buf, self.limits, 0) {
+                Ok(frame) => return Ok(frame),
+                Err(RespError::NotComplete) => self.fill_buf()?,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn fill_buf(&mut self) -> Result<(), RespError> {
+        let mut chunk = [0u8; READ_CHUNK];
+        let n = self.reader.read(&mut chunk)?;
+        if n == 0 {
+            return Err(if self.buf.is_empty() {
+                RespError::Eof
+            } else {
+                RespError::UnexpectedEof
+            });
+        }
+        self.buf.extend_from_slice(&chunk[..n]);
+        Ok(())
+    }
+}
+
+impl<R> AsRef<BytesMut> for Decoder<R> {
+    fn as_ref(&self) -> &BytesMut {
+        &self.buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use anyhow::Result;
+
+    use super::*;
+
+    /// Wraps a fixed buffer and counts [`Input::peek`] calls, so a test
+    /// can assert that decoding one small frame out of a large buffered
+    /// backlog only scans a bounded header probe instead of materializing
+    /// everything available.
+    struct CountingInput {
+        buf: BytesMut,
+        peek_calls: Cell<usize>,
+    }
+
+    impl CountingInput {
+        fn new(data: &[u8]) -> Self {
+            CountingInput {
+                buf: BytesMut::from(data),
+                peek_calls: Cell::new(0),
+            }
+        }
+    }
+
+    impl Input for CountingInput {
+        fn read_byte(&mut self) -> Option<u8> {
+            self.buf.read_byte()
+        }
+
+        fn read(&mut self, into: &mut [u8]) -> usize {
+            self.buf.read(into)
+        }
+
+        fn peek(&self, offset: usize) -> Option<u8> {
+            self.peek_calls.set(self.peek_calls.get() + 1);
+            self.buf.peek(offset)
+        }
+
+        fn remaining(&self) -> usize {
+            self.buf.remaining()
+        }
+    }
+
+    /// A reader that hands back `data` in full on its first `read` call,
+    /// then panics if ever called again — standing in for a blocking
+    /// socket that has nothing further to offer once a complete frame
+    /// has already been sent, so a second `read` there would hang
+    /// forever waiting on the peer.
+    struct OnceThenPanic<'a> {
+        data: Option<&'a [u8]>,
+    }
+
+    impl Read for OnceThenPanic<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match self.data.take() {
+                Some(data) => {
+                    let n = data.len().min(buf.len());
+                    buf[..n].copy_from_slice(&data[..n]);
+                    Ok(n)
+                }
+                None => panic!(
+                    "reader invoked again after yielding a complete frame — \
+                     this would block forever on a live, still-open socket"
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_does_not_over_fetch_a_complete_frame() -> Result<()> {
+        let mut input = super::super::IoInput::new(OnceThenPanic {
+            data: Some(b"+OK\r\n"),
+        });
+        assert_eq!(
+            SimpleString::decode(&mut input, DecodeLimits::default(), 0)?,
+            SimpleString::new("OK")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_small_frame_does_not_scan_whole_backlog() -> Result<()> {
+        let mut wire = b"+OK\r\n".to_vec();
+        wire.extend(std::iter::repeat_n(b'x', 100_000));
+        let mut input = CountingInput::new(&wire);
+
+        let frame = SimpleString::decode(&mut input, DecodeLimits::default(), 0)?;
+
+        assert_eq!(frame, SimpleString::new("OK"));
+        assert!(
+            input.peek_calls.get() < 1000,
+            "expected a bounded header probe, saw {} peek() calls over a {}-byte backlog",
+            input.peek_calls.get(),
+            wire.len()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_simple_string() -> Result<()> {
+        let mut buf = BytesMut::from(&b"+OK\r\n"[..]);
+        assert_eq!(
+            SimpleString::decode(&mut buf, DecodeLimits::default(), 0)?,
+            SimpleString::new("OK")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_integer() -> Result<()> {
+        let mut buf = BytesMut::from(&b":+42\r\n"[..]);
+        assert_eq!(i64::decode(&mut buf, DecodeLimits::default(), 0)?, 42);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_bulk_string() -> Result<()> {
+        let mut buf = BytesMut::from(&b"$5\r\nhello\r\n"[..]);
+        assert_eq!(
+            BulkString::decode(&mut buf, DecodeLimits::default(), 0)?,
+            BulkString::new("hello")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_null_bulk_string() -> Result<()> {
+        let mut buf = BytesMut::from(&b"$-1\r\n"[..]);
+        assert_eq!(
+            RespNullBulkString::decode(&mut buf, DecodeLimits::default(), 0)?,
+            RespNullBulkString
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_array() -> Result<()> {
+        let mut buf = BytesMut::from(&b"*2\r\n$3\r\nset\r\n$5\r\nhello\r\n"[..]);
+        let frame = RespFrame::decode(&mut buf, DecodeLimits::default(), 0)?;
+        assert_eq!(
+            frame,
+            RespArray::new([BulkString::new("set").into(), BulkString::new("hello").into()])
+                .into()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_not_complete() {
+        let mut buf = BytesMut::from(&b"$5\r\nhel"[..]);
+        assert_eq!(
+            BulkString::decode(&mut buf, DecodeLimits::default(), 0),
+            Err(RespError::NotComplete)
+        );
+    }
+
+    #[test]
+    fn test_decode_big_number() -> Result<()> {
+        let mut buf = BytesMut::from(&b"(12345\r\n"[..]);
+        assert_eq!(
+            BigNumber::decode(&mut buf, DecodeLimits::default(), 0)?,
+            BigNumber::new("12345")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_big_number_accepts_negative() -> Result<()> {
+        let mut buf = BytesMut::from(&b"(-12345\r\n"[..]);
+        assert_eq!(
+            BigNumber::decode(&mut buf, DecodeLimits::default(), 0)?,
+            BigNumber::new("-12345")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_big_number_rejects_non_numeric_payload() {
+        let mut buf = BytesMut::from(&b"(not-a-number\r\n"[..]);
+        assert!(matches!(
+            BigNumber::decode(&mut buf, DecodeLimits::default(), 0),
+            Err(RespError::InvalidFrame(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_verbatim_string() -> Result<()> {
+        let mut buf = BytesMut::from(&b"=15\r\ntxt:Some string\r\n"[..]);
+        assert_eq!(
+            RespVerbatimString::decode(&mut buf, DecodeLimits::default(), 0)?,
+            RespVerbatimString::new(*b"txt", "Some string".as_bytes())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decoder_streams_across_reads() -> Result<()> {
+        let wire = b"+OK\r\n:+1\r\n";
+        let mut decoder = Decoder::new(&wire[..]);
+        assert_eq!(decoder.next()?, SimpleString::new("OK").into());
+        assert_eq!(decoder.next()?, 1i64.into());
+        assert_eq!(decoder.next().unwrap_err(), RespError::Eof);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decoder_rejects_frame_beyond_max_depth() {
+        let wire = b"*1\r\n*1\r\n*1\r\n$1\r\nx\r\n";
+        let mut decoder = Decoder::with_limits(&wire[..], DecodeLimits::new(512 * 1024 * 1024, 1));
+        assert_eq!(decoder.next().unwrap_err(), RespError::MaxDepthExceeded(2));
+    }
+
+    #[test]
+    fn test_decoder_rejects_nested_map_beyond_max_depth() {
+        let wire = b"%1\r\n+k\r\n%1\r\n+k\r\n%1\r\n+k\r\n+v\r\n";
+        let mut decoder = Decoder::with_limits(&wire[..], DecodeLimits::new(512 * 1024 * 1024, 1));
+        assert_eq!(decoder.next().unwrap_err(), RespError::MaxDepthExceeded(2));
+    }
+}
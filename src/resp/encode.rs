@@ -1,6 +1,6 @@
 use super::{
-    BulkString, RespArray, RespEncode, RespMap, RespNull, RespNullArray, RespNullBulkString,
-    RespSet, SimpleError, SimpleString,
+    BigNumber, BulkString, RespArray, RespAttribute, RespEncode, RespMap, RespNull, RespNullArray,
+    RespNullBulkString, RespPush, RespSet, RespVerbatimString, SimpleError, SimpleString,
 };
 
 const BUF_CAP: usize = 4096;
@@ -106,6 +106,46 @@ impl RespEncode for RespSet {
     }
 }
 
+impl RespEncode for BigNumber {
+    fn encode(self) -> Vec<u8> {
+        format!("({}\r\n", self.0).into_bytes()
+    }
+}
+
+impl RespEncode for RespVerbatimString {
+    fn encode(self) -> Vec<u8> {
+        let format = String::from_utf8_lossy(&self.format);
+        let mut buf = Vec::with_capacity(self.data.len() + 16);
+        buf.extend_from_slice(&format!("={}\r\n{}:", self.data.len() + 4, format).into_bytes());
+        buf.extend_from_slice(&self.data);
+        buf.extend_from_slice(b"\r\n");
+        buf
+    }
+}
+
+impl RespEncode for RespPush {
+    fn encode(self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(BUF_CAP);
+        buf.extend_from_slice(&format!(">{}\r\n", self.0.len()).into_bytes());
+        for item in self.0 {
+            buf.extend_from_slice(&item.encode());
+        }
+        buf
+    }
+}
+
+impl RespEncode for RespAttribute {
+    fn encode(self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(BUF_CAP);
+        buf.extend_from_slice(&format!("|{}\r\n", self.0.len()).into_bytes());
+        for (key, value) in self.0 {
+            buf.extend_from_slice(&SimpleString::new(key).encode());
+            buf.extend_from_slice(&value.encode());
+        }
+        buf
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::RespFrame;
@@ -213,4 +253,44 @@ mod tests {
             "~2\r\n*2\r\n:+1234\r\n#t\r\n$5\r\nworld\r\n"
         )
     }
+
+    #[test]
+    fn test_encode_big_number() {
+        let frame: RespFrame = BigNumber::new("3492890328409238509324850943850943825024385").into();
+        assert_eq!(
+            frame.encode(),
+            b"(3492890328409238509324850943850943825024385\r\n".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_encode_verbatim_string() {
+        let frame: RespFrame = RespVerbatimString::new(*b"txt", "Some string".as_bytes()).into();
+        assert_eq!(frame.encode(), b"=15\r\ntxt:Some string\r\n".to_vec());
+    }
+
+    #[test]
+    fn test_encode_push() {
+        let frame: RespFrame = RespPush::new([
+            SimpleString::new("pubsub").into(),
+            BulkString::new("message").into(),
+        ])
+        .into();
+        assert_eq!(
+            frame.encode(),
+            b">2\r\n+pubsub\r\n$7\r\nmessage\r\n".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_encode_attribute() {
+        let mut attr = RespAttribute::new();
+        attr.insert("key-popularity".to_string(), BulkString::new("a").into());
+
+        let frame: RespFrame = attr.into();
+        assert_eq!(
+            frame.encode(),
+            b"|1\r\n+key-popularity\r\n$1\r\na\r\n".to_vec()
+        );
+    }
 }
@@ -0,0 +1,563 @@
+use super::{
+    decode::is_big_number, extract_simple_frame_data, find_crlf, parse_length_checked, BigNumber,
+    BulkString, DecodeLimits, RespArray, RespAttribute, RespError, RespFrame, RespMap, RespNull,
+    RespNullArray, RespNullBulkString, RespPush, RespSet, RespVerbatimString, SimpleError,
+    SimpleString, CRLF_LEN,
+};
+
+/// Borrowed counterpart of [`RespFrame`].
+///
+/// Text and bulk variants hold `&'a [u8]` slices pointing straight into
+/// the buffer that was decoded, so inspecting a frame never allocates.
+/// Call [`RespFrameRef::to_owned`] when a variant needs to outlive the
+/// source buffer.
+#[derive(Debug, PartialEq)]
+pub enum RespFrameRef<'a> {
+    SimpleString(&'a [u8]),
+    Error(&'a [u8]),
+    Integer(i64),
+    BulkString(&'a [u8]),
+    NullBulkString,
+    Array(Vec<RespFrameRef<'a>>),
+    Null,
+    NullArray,
+    Boolean(bool),
+    Double(f64),
+    Map(Vec<(&'a [u8], RespFrameRef<'a>)>),
+    Set(Vec<RespFrameRef<'a>>),
+    BigNumber(&'a [u8]),
+    VerbatimString { format: &'a [u8], data: &'a [u8] },
+    Push(Vec<RespFrameRef<'a>>),
+    Attribute(Vec<(&'a [u8], RespFrameRef<'a>)>),
+}
+
+/// Decode a single frame from `buf` without copying its text or bulk
+/// payloads, returning the frame together with the number of bytes it
+/// consumed. Nested frames (arrays, maps, sets) are validated the same
+/// way the owned decoder validates them, but their leaves borrow `buf`.
+///
+/// Uses [`DecodeLimits::default`]; see [`decode_ref_with_limits`] for a
+/// caller-configurable frame length / nesting depth bound.
+pub fn decode_ref(buf: &[u8]) -> Result<(RespFrameRef<'_>, usize), RespError> {
+    decode_ref_with_limits(buf, DecodeLimits::default())
+}
+
+/// Like [`decode_ref`], but with caller-supplied bounds on frame length
+/// and nesting depth, matching [`super::RespDecode`]'s guard against a
+/// deeply nested frame driving unbounded recursion.
+pub fn decode_ref_with_limits(
+    buf: &[u8],
+    limits: DecodeLimits,
+) -> Result<(RespFrameRef<'_>, usize), RespError> {
+    decode_ref_at(buf, limits, 0)
+}
+
+/// Guards against a frame like `*1\r\n*1\r\n*1\r\n...` driving unbounded
+/// recursion, mirroring the check [`super::calc_total_length`] applies
+/// on the owned decode path.
+fn check_depth(depth: usize, limits: DecodeLimits) -> Result<(), RespError> {
+    if depth > limits.max_depth {
+        return Err(RespError::MaxDepthExceeded(depth));
+    }
+    Ok(())
+}
+
+fn decode_ref_at(
+    buf: &[u8],
+    limits: DecodeLimits,
+    depth: usize,
+) -> Result<(RespFrameRef<'_>, usize), RespError> {
+    if buf.is_empty() {
+        return Err(RespError::NotComplete);
+    }
+    match buf[0] {
+        b'+' => {
+            let end = extract_simple_frame_data(buf, "+")?;
+            Ok((RespFrameRef::SimpleString(&buf[1..end]), end + CRLF_LEN))
+        }
+        b'-' => {
+            let end = extract_simple_frame_data(buf, "-")?;
+            Ok((RespFrameRef::Error(&buf[1..end]), end + CRLF_LEN))
+        }
+        b':' => {
+            let end = extract_simple_frame_data(buf, ":")?;
+            let i: i64 = String::from_utf8_lossy(&buf[1..end]).parse()?;
+            Ok((RespFrameRef::Integer(i), end + CRLF_LEN))
+        }
+        b'#' => {
+            let end = extract_simple_frame_data(buf, "#")?;
+            let b = match &buf[1..end] {
+                b"t" => true,
+                b"f" => false,
+                other => {
+                    return Err(RespError::InvalidFrame(format!(
+                        "invalid boolean: {:?}",
+                        other
+                    )))
+                }
+            };
+            Ok((RespFrameRef::Boolean(b), end + CRLF_LEN))
+        }
+        b',' => {
+            let end = extract_simple_frame_data(buf, ",")?;
+            let d: f64 = String::from_utf8_lossy(&buf[1..end]).parse()?;
+            Ok((RespFrameRef::Double(d), end + CRLF_LEN))
+        }
+        b'_' => {
+            let end = find_crlf(buf, 1).ok_or(RespError::NotComplete)?;
+            Ok((RespFrameRef::Null, end + CRLF_LEN))
+        }
+        b'$' => {
+            if buf.starts_with(b"$-1\r\n") {
+                return Ok((RespFrameRef::NullBulkString, 5));
+            }
+            let (end, len) = parse_length_checked(buf, "$", limits)?;
+            let total = end + CRLF_LEN + len + CRLF_LEN;
+            if buf.len() < total {
+                return Err(RespError::NotComplete);
+            }
+            let data = &buf[end + CRLF_LEN..end + CRLF_LEN + len];
+            Ok((RespFrameRef::BulkString(data), total))
+        }
+        b'(' => {
+            let end = extract_simple_frame_data(buf, "(")?;
+            let s = String::from_utf8_lossy(&buf[1..end]);
+            if !is_big_number(&s) {
+                return Err(RespError::InvalidFrame(format!("invalid big number: {:?}", s)));
+            }
+            Ok((RespFrameRef::BigNumber(&buf[1..end]), end + CRLF_LEN))
+        }
+        b'=' => {
+            let (end, len) = parse_length_checked(buf, "=", limits)?;
+            let total = end + CRLF_LEN + len + CRLF_LEN;
+            if buf.len() < total {
+                return Err(RespError::NotComplete);
+            }
+            let body = &buf[end + CRLF_LEN..end + CRLF_LEN + len];
+            if body.len() < 4 {
+                return Err(RespError::InvalidFrame(
+                    "verbatim string format tag must be 3 bytes".to_string(),
+                ));
+            }
+            Ok((
+                RespFrameRef::VerbatimString {
+                    format: &body[..3],
+                    data: &body[4..],
+                },
+                total,
+            ))
+        }
+        b'*' => {
+            if buf.starts_with(b"*-1\r\n") {
+                return Ok((RespFrameRef::NullArray, 5));
+            }
+            check_depth(depth, limits)?;
+            let (end, len) = parse_length_checked(buf, "*", limits)?;
+            let mut consumed = end + CRLF_LEN;
+            let mut items = Vec::with_capacity(len.min(buf.len()));
+            for _ in 0..len {
+                let (item, n) = decode_ref_at(&buf[consumed..], limits, depth + 1)?;
+                items.push(item);
+                consumed += n;
+            }
+            Ok((RespFrameRef::Array(items), consumed))
+        }
+        b'~' => {
+            check_depth(depth, limits)?;
+            let (end, len) = parse_length_checked(buf, "~", limits)?;
+            let mut consumed = end + CRLF_LEN;
+            let mut items = Vec::with_capacity(len.min(buf.len()));
+            for _ in 0..len {
+                let (item, n) = decode_ref_at(&buf[consumed..], limits, depth + 1)?;
+                items.push(item);
+                consumed += n;
+            }
+            Ok((RespFrameRef::Set(items), consumed))
+        }
+        b'>' => {
+            check_depth(depth, limits)?;
+            let (end, len) = parse_length_checked(buf, ">", limits)?;
+            let mut consumed = end + CRLF_LEN;
+            let mut items = Vec::with_capacity(len.min(buf.len()));
+            for _ in 0..len {
+                let (item, n) = decode_ref_at(&buf[consumed..], limits, depth + 1)?;
+                items.push(item);
+                consumed += n;
+            }
+            Ok((RespFrameRef::Push(items), consumed))
+        }
+        b'%' => {
+            check_depth(depth, limits)?;
+            let (end, len) = parse_length_checked(buf, "%", limits)?;
+            let mut consumed = end + CRLF_LEN;
+            let mut entries = Vec::with_capacity(len.min(buf.len()));
+            for _ in 0..len {
+                let key_end = extract_simple_frame_data(&buf[consumed..], "+")?;
+                let key = &buf[consumed + 1..consumed + key_end];
+                consumed += key_end + CRLF_LEN;
+                let (value, n) = decode_ref_at(&buf[consumed..], limits, depth + 1)?;
+                consumed += n;
+                entries.push((key, value));
+            }
+            Ok((RespFrameRef::Map(entries), consumed))
+        }
+        b'|' => {
+            check_depth(depth, limits)?;
+            let (end, len) = parse_length_checked(buf, "|", limits)?;
+            let mut consumed = end + CRLF_LEN;
+            let mut entries = Vec::with_capacity(len.min(buf.len()));
+            for _ in 0..len {
+                let key_end = extract_simple_frame_data(&buf[consumed..], "+")?;
+                let key = &buf[consumed + 1..consumed + key_end];
+                consumed += key_end + CRLF_LEN;
+                let (value, n) = decode_ref_at(&buf[consumed..], limits, depth + 1)?;
+                consumed += n;
+                entries.push((key, value));
+            }
+            Ok((RespFrameRef::Attribute(entries), consumed))
+        }
+        other => Err(RespError::InvalidFrameType(format!(
+            "unsupported prefix for borrowed decode: {:?}",
+            other as char
+        ))),
+    }
+}
+
+impl<'a> RespFrameRef<'a> {
+    /// Copy this frame's borrowed bytes into an owned [`RespFrame`].
+    pub fn to_owned(&self) -> RespFrame {
+        match self {
+            RespFrameRef::SimpleString(s) => {
+                SimpleString::new(String::from_utf8_lossy(s).into_owned()).into()
+            }
+            RespFrameRef::Error(s) => {
+                SimpleError::new(String::from_utf8_lossy(s).into_owned()).into()
+            }
+            RespFrameRef::Integer(i) => (*i).into(),
+            RespFrameRef::BulkString(b) => BulkString::new(b.to_vec()).into(),
+            RespFrameRef::NullBulkString => RespNullBulkString.into(),
+            RespFrameRef::Array(items) => {
+                RespArray::new(items.iter().map(RespFrameRef::to_owned).collect::<Vec<_>>()).into()
+            }
+            RespFrameRef::Null => RespNull.into(),
+            RespFrameRef::NullArray => RespNullArray.into(),
+            RespFrameRef::Boolean(b) => (*b).into(),
+            RespFrameRef::Double(d) => (*d).into(),
+            RespFrameRef::Map(entries) => {
+                let mut map = RespMap::new();
+                for (key, value) in entries {
+                    map.insert(String::from_utf8_lossy(key).into_owned(), value.to_owned());
+                }
+                map.into()
+            }
+            RespFrameRef::Set(items) => {
+                RespSet::new(items.iter().map(RespFrameRef::to_owned).collect::<Vec<_>>()).into()
+            }
+            RespFrameRef::BigNumber(s) => {
+                BigNumber::new(String::from_utf8_lossy(s).into_owned()).into()
+            }
+            RespFrameRef::VerbatimString { format, data } => {
+                let format: [u8; 3] = (*format).try_into().expect("format tag is always 3 bytes");
+                RespVerbatimString::new(format, data.to_vec()).into()
+            }
+            RespFrameRef::Push(items) => {
+                RespPush::new(items.iter().map(RespFrameRef::to_owned).collect::<Vec<_>>()).into()
+            }
+            RespFrameRef::Attribute(entries) => {
+                let mut attr = RespAttribute::new();
+                for (key, value) in entries {
+                    attr.insert(String::from_utf8_lossy(key).into_owned(), value.to_owned());
+                }
+                attr.into()
+            }
+        }
+    }
+
+    /// Append this frame's wire representation to `buf` without any
+    /// intermediate allocation beyond growing `buf` itself.
+    pub fn encode_to(&self, buf: &mut Vec<u8>) {
+        use std::io::Write;
+        match self {
+            RespFrameRef::SimpleString(s) => {
+                buf.push(b'+');
+                buf.extend_from_slice(s);
+                buf.extend_from_slice(super::CRLF);
+            }
+            RespFrameRef::Error(s) => {
+                buf.push(b'-');
+                buf.extend_from_slice(s);
+                buf.extend_from_slice(super::CRLF);
+            }
+            RespFrameRef::Integer(i) => {
+                let sign = if *i < 0 { "" } else { "+" };
+                let _ = write!(buf, ":{}{}\r\n", sign, i);
+            }
+            RespFrameRef::BulkString(b) => {
+                let _ = write!(buf, "${}\r\n", b.len());
+                buf.extend_from_slice(b);
+                buf.extend_from_slice(super::CRLF);
+            }
+            RespFrameRef::NullBulkString => buf.extend_from_slice(b"$-1\r\n"),
+            RespFrameRef::Array(items) => {
+                let _ = write!(buf, "*{}\r\n", items.len());
+                for item in items {
+                    item.encode_to(buf);
+                }
+            }
+            RespFrameRef::Null => buf.extend_from_slice(b"_\r\n"),
+            RespFrameRef::NullArray => buf.extend_from_slice(b"*-1\r\n"),
+            RespFrameRef::Boolean(b) => {
+                buf.extend_from_slice(if *b { b"#t\r\n" } else { b"#f\r\n" })
+            }
+            RespFrameRef::Double(d) => {
+                let _ = if d.abs() > 1e+8 || d.abs() < 1e-8 {
+                    write!(buf, ",{:+e}\r\n", d)
+                } else {
+                    let sign = if *d < 0.0 { "" } else { "+" };
+                    write!(buf, ",{}{}\r\n", sign, d)
+                };
+            }
+            RespFrameRef::Map(entries) => {
+                let _ = write!(buf, "%{}\r\n", entries.len());
+                for (key, value) in entries {
+                    RespFrameRef::SimpleString(key).encode_to(buf);
+                    value.encode_to(buf);
+                }
+            }
+            RespFrameRef::Set(items) => {
+                let _ = write!(buf, "~{}\r\n", items.len());
+                for item in items {
+                    item.encode_to(buf);
+                }
+            }
+            RespFrameRef::BigNumber(s) => {
+                buf.push(b'(');
+                buf.extend_from_slice(s);
+                buf.extend_from_slice(super::CRLF);
+            }
+            RespFrameRef::VerbatimString { format, data } => {
+                let _ = write!(buf, "={}\r\n", data.len() + 4);
+                buf.extend_from_slice(format);
+                buf.push(b':');
+                buf.extend_from_slice(data);
+                buf.extend_from_slice(super::CRLF);
+            }
+            RespFrameRef::Push(items) => {
+                let _ = write!(buf, ">{}\r\n", items.len());
+                for item in items {
+                    item.encode_to(buf);
+                }
+            }
+            RespFrameRef::Attribute(entries) => {
+                let _ = write!(buf, "|{}\r\n", entries.len());
+                for (key, value) in entries {
+                    RespFrameRef::SimpleString(key).encode_to(buf);
+                    value.encode_to(buf);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_ref_simple_string() -> Result<()> {
+        let (frame, n) = decode_ref(b"+OK\r\n")?;
+        assert_eq!(frame, RespFrameRef::SimpleString(b"OK"));
+        assert_eq!(n, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_error() -> Result<()> {
+        let (frame, n) = decode_ref(b"-oops\r\n")?;
+        assert_eq!(frame, RespFrameRef::Error(b"oops"));
+        assert_eq!(n, 7);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_integer() -> Result<()> {
+        let (frame, _) = decode_ref(b":+42\r\n")?;
+        assert_eq!(frame, RespFrameRef::Integer(42));
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_boolean() -> Result<()> {
+        let (frame, _) = decode_ref(b"#t\r\n")?;
+        assert_eq!(frame, RespFrameRef::Boolean(true));
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_rejects_invalid_boolean() {
+        assert!(matches!(
+            decode_ref(b"#x\r\n"),
+            Err(RespError::InvalidFrame(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_ref_double() -> Result<()> {
+        let (frame, _) = decode_ref(b",+2.5\r\n")?;
+        assert_eq!(frame, RespFrameRef::Double(2.5));
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_null() -> Result<()> {
+        let (frame, n) = decode_ref(b"_\r\n")?;
+        assert_eq!(frame, RespFrameRef::Null);
+        assert_eq!(n, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_null_array() -> Result<()> {
+        let (frame, n) = decode_ref(b"*-1\r\n")?;
+        assert_eq!(frame, RespFrameRef::NullArray);
+        assert_eq!(n, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_null_bulk_string() -> Result<()> {
+        let (frame, n) = decode_ref(b"$-1\r\n")?;
+        assert_eq!(frame, RespFrameRef::NullBulkString);
+        assert_eq!(n, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_bulk_string() -> Result<()> {
+        let (frame, n) = decode_ref(b"$5\r\nhello\r\n")?;
+        assert_eq!(frame, RespFrameRef::BulkString(b"hello"));
+        assert_eq!(n, 11);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_not_complete() {
+        assert_eq!(decode_ref(b"$5\r\nhel"), Err(RespError::NotComplete));
+    }
+
+    #[test]
+    fn test_decode_ref_big_number() -> Result<()> {
+        let (frame, _) = decode_ref(b"(12345\r\n")?;
+        assert_eq!(frame, RespFrameRef::BigNumber(b"12345"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_rejects_non_numeric_big_number() {
+        assert!(matches!(
+            decode_ref(b"(not-a-number\r\n"),
+            Err(RespError::InvalidFrame(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_ref_verbatim_string() -> Result<()> {
+        let (frame, _) = decode_ref(b"=15\r\ntxt:Some string\r\n")?;
+        assert_eq!(
+            frame,
+            RespFrameRef::VerbatimString {
+                format: b"txt",
+                data: b"Some string"
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_array() -> Result<()> {
+        let (frame, n) = decode_ref(b"*2\r\n$3\r\nset\r\n$5\r\nhello\r\n")?;
+        assert_eq!(
+            frame,
+            RespFrameRef::Array(vec![
+                RespFrameRef::BulkString(b"set"),
+                RespFrameRef::BulkString(b"hello"),
+            ])
+        );
+        assert_eq!(n, 24);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_set() -> Result<()> {
+        let (frame, _) = decode_ref(b"~2\r\n:+1\r\n:+2\r\n")?;
+        assert_eq!(
+            frame,
+            RespFrameRef::Set(vec![RespFrameRef::Integer(1), RespFrameRef::Integer(2)])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_push() -> Result<()> {
+        let (frame, _) = decode_ref(b">1\r\n+hi\r\n")?;
+        assert_eq!(frame, RespFrameRef::Push(vec![RespFrameRef::SimpleString(b"hi")]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_map() -> Result<()> {
+        let (frame, _) = decode_ref(b"%1\r\n+k\r\n+v\r\n")?;
+        assert_eq!(
+            frame,
+            RespFrameRef::Map(vec![(&b"k"[..], RespFrameRef::SimpleString(b"v"))])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_attribute() -> Result<()> {
+        let (frame, _) = decode_ref(b"|1\r\n+k\r\n+v\r\n")?;
+        assert_eq!(
+            frame,
+            RespFrameRef::Attribute(vec![(&b"k"[..], RespFrameRef::SimpleString(b"v"))])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_ref_rejects_frame_beyond_max_depth() {
+        let wire = b"*1\r\n*1\r\n*1\r\n$1\r\nx\r\n";
+        let limits = DecodeLimits::new(512 * 1024 * 1024, 1);
+        assert_eq!(
+            decode_ref_with_limits(wire, limits).unwrap_err(),
+            RespError::MaxDepthExceeded(2)
+        );
+    }
+
+    #[test]
+    fn test_to_owned_and_encode_to_round_trip() -> Result<()> {
+        let wire = b"*2\r\n$3\r\nset\r\n%1\r\n+k\r\n:+1\r\n";
+        let (frame, n) = decode_ref(wire)?;
+        assert_eq!(n, wire.len());
+
+        assert_eq!(
+            frame.to_owned(),
+            RespArray::new([
+                BulkString::new("set").into(),
+                {
+                    let mut map = RespMap::new();
+                    map.insert("k".to_string(), 1i64.into());
+                    map.into()
+                },
+            ])
+            .into()
+        );
+
+        let mut encoded = Vec::new();
+        frame.encode_to(&mut encoded);
+        assert_eq!(encoded, wire);
+        Ok(())
+    }
+}
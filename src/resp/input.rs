@@ -0,0 +1,236 @@
+use std::{cell::RefCell, io::Read};
+
+use bytes::{Buf, BytesMut};
+
+/// A source of bytes that RESP decoding can pull from without assuming
+/// a particular buffer type.
+///
+/// Blanket implementations cover an in-memory slice, a [`BytesMut`],
+/// and any [`Read`]er (see [`IoInput`]), so the same decode helpers
+/// serve slices, network streams and the streaming [`Decoder`](super::Decoder)
+/// alike.
+pub trait Input {
+    /// Consume and return the next byte, or `None` if exhausted.
+    fn read_byte(&mut self) -> Option<u8>;
+    /// Consume up to `into.len()` bytes, returning how many were read.
+    fn read(&mut self, into: &mut [u8]) -> usize;
+    /// Look `offset` bytes ahead of the current position without
+    /// consuming anything.
+    fn peek(&self, offset: usize) -> Option<u8>;
+    /// Number of bytes currently available. For an in-memory source this
+    /// never blocks; an [`IoInput`] may issue one read on its reader here.
+    fn remaining(&self) -> usize;
+}
+
+impl Input for &[u8] {
+    fn read_byte(&mut self) -> Option<u8> {
+        let (&b, rest) = self.split_first()?;
+        *self = rest;
+        Some(b)
+    }
+
+    fn read(&mut self, into: &mut [u8]) -> usize {
+        let n = into.len().min(self.len());
+        into[..n].copy_from_slice(&self[..n]);
+        *self = &self[n..];
+        n
+    }
+
+    fn peek(&self, offset: usize) -> Option<u8> {
+        self.get(offset).copied()
+    }
+
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+}
+
+impl Input for BytesMut {
+    fn read_byte(&mut self) -> Option<u8> {
+        if self.is_empty() {
+            return None;
+        }
+        let b = self[0];
+        self.advance(1);
+        Some(b)
+    }
+
+    fn read(&mut self, into: &mut [u8]) -> usize {
+        let n = into.len().min(self.len());
+        into[..n].copy_from_slice(&self[..n]);
+        self.advance(n);
+        n
+    }
+
+    fn peek(&self, offset: usize) -> Option<u8> {
+        self.get(offset).copied()
+    }
+
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Adapts any [`Read`]er into an [`Input`], blocking on the underlying
+/// reader whenever a lookahead needs bytes that haven't arrived yet.
+///
+/// `reader` and `lookahead` sit behind a [`RefCell`] so that `peek` and
+/// `remaining` — which the [`Input`] contract requires to take `&self`,
+/// since the by-value length-parsing helpers need to call them without
+/// holding a mutable borrow — can still top up the lookahead buffer from
+/// the underlying reader on demand.
+pub struct IoInput<R> {
+    reader: RefCell<R>,
+    lookahead: RefCell<Vec<u8>>,
+}
+
+impl<R: Read> IoInput<R> {
+    pub fn new(reader: R) -> Self {
+        IoInput {
+            reader: RefCell::new(reader),
+            lookahead: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn fill_to(&self, n: usize) {
+        let mut lookahead = self.lookahead.borrow_mut();
+        if lookahead.len() >= n {
+            return;
+        }
+        let mut reader = self.reader.borrow_mut();
+        let mut chunk = [0u8; 4096];
+        while lookahead.len() < n {
+            match reader.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(read) => lookahead.extend_from_slice(&chunk[..read]),
+                Err(_) => break,
+            }
+        }
+    }
+
+    /// Pull in whatever the reader currently has ready, without demanding
+    /// a specific byte count. Used by [`Input::remaining`], which has no
+    /// target length to aim `fill_to` at.
+    ///
+    /// Issues a single `read` call — looping until the reader returns
+    /// `Ok(0)` would mean blocking until EOF on a live socket. That one
+    /// call still blocks if the reader has nothing ready. A single
+    /// call is enough as long as `remaining` keeps calling it on every
+    /// invocation (see the caller): `Read::read` is allowed to return
+    /// short, so a reader that hands back its payload a few bytes at a
+    /// time (typical of sockets and pipes) still tops up fully, just
+    /// spread across the caller's retries instead of in one shot.
+    fn fill_once(&self) {
+        let mut lookahead = self.lookahead.borrow_mut();
+        let mut reader = self.reader.borrow_mut();
+        let mut chunk = [0u8; 4096];
+        if let Ok(read) = reader.read(&mut chunk) {
+            lookahead.extend_from_slice(&chunk[..read]);
+        }
+    }
+}
+
+impl<R: Read> Input for IoInput<R> {
+    fn read_byte(&mut self) -> Option<u8> {
+        self.fill_to(1);
+        let mut lookahead = self.lookahead.borrow_mut();
+        if lookahead.is_empty() {
+            None
+        } else {
+            Some(lookahead.remove(0))
+        }
+    }
+
+    fn read(&mut self, into: &mut [u8]) -> usize {
+        self.fill_to(into.len());
+        let mut lookahead = self.lookahead.borrow_mut();
+        let n = into.len().min(lookahead.len());
+        into[..n].copy_from_slice(&lookahead[..n]);
+        lookahead.drain(..n);
+        n
+    }
+
+    fn peek(&self, offset: usize) -> Option<u8> {
+        self.fill_to(offset + 1);
+        self.lookahead.borrow().get(offset).copied()
+    }
+
+    fn remaining(&self) -> usize {
+        // Always attempt to top up, not just when the lookahead is
+        // empty: once it holds even one byte, a reader that only ever
+        // hands back a few bytes per call would otherwise never be
+        // asked for more, even across repeated retries from a decode
+        // loop driven off `NotComplete`.
+        self.fill_once();
+        self.lookahead.borrow().len()
+    }
+}
+
+/// Collects up to `limit` bytes currently available from `input` without
+/// consuming them, for the decode paths that still need slice semantics
+/// (e.g. the recursive length math for nested frames).
+///
+/// Takes `available` (the caller's own, already-taken snapshot of
+/// [`Input::remaining`]) rather than calling `remaining()` itself: for
+/// an [`IoInput`], every call to `remaining()` can trigger a blocking
+/// read, so a decode attempt must take that snapshot exactly once and
+/// thread it through instead of re-querying it per helper call. Callers
+/// that need everything buffered pass `available` itself as `limit`;
+/// callers that only need a frame's own header (not a trailing backlog
+/// of already-buffered pipelined frames) pass something smaller.
+pub(super) fn materialize_upto<I: Input>(input: &I, available: usize, limit: usize) -> Vec<u8> {
+    let n = available.min(limit);
+    let mut out = Vec::with_capacity(n);
+    for i in 0..n {
+        match input.peek(i) {
+            Some(b) => out.push(b),
+            None => break,
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A reader that only ever hands back a single byte per `read`
+    /// call, regardless of how much the caller asked for — the
+    /// short-read behavior `Read::read` is explicitly allowed to
+    /// exhibit, and that sockets/pipes exhibit routinely.
+    struct OneByteAtATime<'a>(&'a [u8]);
+
+    impl Read for OneByteAtATime<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.0.is_empty() || buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = self.0[0];
+            self.0 = &self.0[1..];
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn test_io_input_remaining_tops_up_across_retries() {
+        let wire = b"hello";
+        let input = IoInput::new(OneByteAtATime(wire));
+        for expected in 1..=wire.len() {
+            assert_eq!(input.remaining(), expected);
+        }
+    }
+
+    #[test]
+    fn test_io_input_read_byte_recovers_from_partial_reads() {
+        let wire = b"hello";
+        let mut input = IoInput::new(OneByteAtATime(wire));
+        for _ in 0..wire.len() {
+            input.remaining();
+        }
+        let mut out = Vec::new();
+        while let Some(b) = input.read_byte() {
+            out.push(b);
+        }
+        assert_eq!(out, wire);
+    }
+}
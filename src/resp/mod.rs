@@ -1,12 +1,21 @@
+#[cfg(feature = "bounded-encode")]
+mod bounded_encode;
 mod decode;
 mod encode;
+mod frame_ref;
+mod input;
+
+#[cfg(feature = "bounded-encode")]
+pub use bounded_encode::{BoundedEncode, CapacityError};
+pub use decode::Decoder;
+pub use frame_ref::{decode_ref, decode_ref_with_limits, RespFrameRef};
+pub use input::{IoInput, Input};
 
 use std::{
     collections::BTreeMap,
     ops::{Deref, DerefMut},
 };
 
-use bytes::BytesMut;
 use enum_dispatch::enum_dispatch;
 use thiserror::Error;
 
@@ -20,8 +29,8 @@ pub trait RespEncode {
 
 pub trait RespDecode: Sized {
     const PREFIX: &'static str;
-    fn decode(buf: &mut BytesMut) -> Result<Self, RespError>;
-    fn expect_length(buf: &[u8]) -> Result<usize, RespError>;
+    fn decode<I: Input>(input: &mut I, limits: DecodeLimits, depth: usize) -> Result<Self, RespError>;
+    fn expect_length(buf: &[u8], limits: DecodeLimits, depth: usize) -> Result<usize, RespError>;
 }
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -34,12 +43,28 @@ pub enum RespError {
     InvalidFrameLength(String),
     #[error("Frame is not complete")]
     NotComplete,
+    #[error("Reader exhausted cleanly between frames")]
+    Eof,
+    #[error("Reader exhausted in the middle of a frame")]
+    UnexpectedEof,
+    #[error("I/O error: {0}")]
+    Io(String),
+    #[error("Frame too large: length {0} exceeds the configured limit")]
+    FrameTooLarge(usize),
+    #[error("Nesting depth {0} exceeds the configured limit")]
+    MaxDepthExceeded(usize),
     #[error("Parse int error: {0}")]
     ParseIntError(#[from] std::num::ParseIntError),
     #[error("Parse float error: {0}")]
     ParseFloatError(#[from] std::num::ParseFloatError),
 }
 
+impl From<std::io::Error> for RespError {
+    fn from(e: std::io::Error) -> Self {
+        RespError::Io(e.to_string())
+    }
+}
+
 #[enum_dispatch(RespEncode)]
 #[derive(Debug, PartialEq, PartialOrd)]
 pub enum RespFrame {
@@ -55,6 +80,10 @@ pub enum RespFrame {
     Double(f64),
     Map(RespMap),
     Set(RespSet),
+    BigNumber(BigNumber),
+    VerbatimString(RespVerbatimString),
+    Push(RespPush),
+    Attribute(RespAttribute),
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, PartialOrd)]
@@ -75,6 +104,17 @@ pub struct RespArray(pub Vec<RespFrame>);
 pub struct RespMap(BTreeMap<String, RespFrame>);
 #[derive(Debug, PartialEq, PartialOrd)]
 pub struct RespSet(Vec<RespFrame>);
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd)]
+pub struct BigNumber(String);
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd)]
+pub struct RespVerbatimString {
+    format: [u8; 3],
+    data: Vec<u8>,
+}
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct RespPush(Vec<RespFrame>);
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct RespAttribute(BTreeMap<String, RespFrame>);
 
 impl Deref for SimpleString {
     type Target = String;
@@ -124,6 +164,44 @@ impl Deref for RespArray {
     }
 }
 
+impl Deref for BigNumber {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Deref for RespVerbatimString {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl Deref for RespPush {
+    type Target = Vec<RespFrame>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Deref for RespAttribute {
+    type Target = BTreeMap<String, RespFrame>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for RespAttribute {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 impl From<Vec<RespFrame>> for RespArray {
     fn from(value: Vec<RespFrame>) -> Self {
         RespArray(value)
@@ -202,34 +280,143 @@ impl RespSet {
     }
 }
 
-fn parse_length(buf: &[u8], prefix: &str) -> Result<(usize, usize), RespError> {
-    let end = extract_simple_frame_data(buf, prefix)?;
-    let s = String::from_utf8_lossy(&buf[prefix.len()..end]);
-    Ok((end, s.parse()?))
+impl BigNumber {
+    pub fn new(s: impl Into<String>) -> Self {
+        BigNumber(s.into())
+    }
+}
+
+impl From<&str> for BigNumber {
+    fn from(s: &str) -> Self {
+        BigNumber(s.to_string())
+    }
+}
+
+impl RespVerbatimString {
+    pub fn new(format: [u8; 3], data: impl Into<Vec<u8>>) -> Self {
+        RespVerbatimString {
+            format,
+            data: data.into(),
+        }
+    }
+}
+
+impl RespPush {
+    pub fn new(s: impl Into<Vec<RespFrame>>) -> Self {
+        RespPush(s.into())
+    }
+}
+
+impl RespAttribute {
+    pub fn new() -> Self {
+        RespAttribute(BTreeMap::new())
+    }
+}
+
+impl Default for RespAttribute {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Bounds applied while decoding to guard against a malicious or
+/// malformed length prefix (e.g. `*1000000000\r\n`) driving unbounded
+/// allocation or unbounded recursion into nested frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    pub max_frame_len: usize,
+    pub max_depth: usize,
+}
+
+impl DecodeLimits {
+    pub const fn new(max_frame_len: usize, max_depth: usize) -> Self {
+        DecodeLimits {
+            max_frame_len,
+            max_depth,
+        }
+    }
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        // Generous enough for any real reply, small enough that a bogus
+        // length prefix fails fast instead of reserving gigabytes.
+        DecodeLimits::new(512 * 1024 * 1024, 128)
+    }
+}
+
+fn parse_length<I: Input + Copy>(input: I, prefix: &str) -> Result<(usize, usize), RespError> {
+    let end = extract_simple_frame_data(input, prefix)?;
+    let mut digits = String::with_capacity(end - prefix.len());
+    for i in prefix.len()..end {
+        digits.push(input.peek(i).ok_or(RespError::NotComplete)? as char);
+    }
+    Ok((end, digits.parse()?))
+}
+
+/// Like [`parse_length`], but rejects a length that exceeds
+/// `limits.max_frame_len` with `RespError::FrameTooLarge` instead of
+/// letting the caller reserve that much capacity.
+fn parse_length_checked(
+    buf: &[u8],
+    prefix: &str,
+    limits: DecodeLimits,
+) -> Result<(usize, usize), RespError> {
+    let (end, len) = parse_length(buf, prefix)?;
+    if len > limits.max_frame_len {
+        return Err(RespError::FrameTooLarge(len));
+    }
+    Ok((end, len))
 }
 
 // Array *<number-of-elements>\r\n<element-1>...<element-n>
 // Set ~<number-of-elements>\r\n<element-1>...<element-n>
 // Map %<number-of-entries>\r\n<key-1><value-1>...<key-n><value-n>
-fn calc_total_length(buf: &[u8], end: usize, len: usize, prefix: &str) -> Result<usize, RespError> {
+// Push ><number-of-elements>\r\n<element-1>...<element-n>
+// Attribute |<number-of-entries>\r\n<key-1><value-1>...<key-n><value-n>
+//
+// `depth` is the nesting level of the container whose length we're
+// computing; it is checked against `limits.max_depth` before recursing
+// into any element so a frame like `*1\r\n*1\r\n*1\r\n...` can't drive
+// unbounded recursion.
+fn calc_total_length(
+    buf: &[u8],
+    end: usize,
+    len: usize,
+    prefix: &str,
+    limits: DecodeLimits,
+    depth: usize,
+) -> Result<usize, RespError> {
+    if depth > limits.max_depth {
+        return Err(RespError::MaxDepthExceeded(depth));
+    }
     let mut total = end + CRLF_LEN;
     let mut data = &buf[total..];
     match prefix {
-        "*" | "~" => {
+        "*" | "~" | ">" => {
             for _ in 0..len {
-                let len = RespFrame::expect_length(data)?;
+                let len = RespFrame::expect_length(data, limits, depth + 1)?;
+                if len > data.len() {
+                    return Err(RespError::NotComplete);
+                }
                 data = &data[len..];
                 total += len;
             }
             Ok(total)
         }
-        "%" => {
+        "%" | "|" => {
             for _ in 0..len {
-                let len = SimpleString::expect_length(data)?;
+                let len = SimpleString::expect_length(data, limits, depth + 1)?;
+                if len > data.len() {
+                    return Err(RespError::NotComplete);
+                }
                 data = &data[len..];
                 total += len;
 
-                let len = RespFrame::expect_length(data)?;
+                let len = RespFrame::expect_length(data, limits, depth + 1)?;
+                if len > data.len() {
+                    return Err(RespError::NotComplete);
+                }
                 data = &data[len..];
                 total += len;
             }
@@ -240,26 +427,32 @@ fn calc_total_length(buf: &[u8], end: usize, len: usize, prefix: &str) -> Result
     }
 }
 
-fn extract_simple_frame_data(buf: &[u8], prefix: &str) -> Result<usize, RespError> {
-    if buf.len() < 3 {
+fn extract_simple_frame_data<I: Input + Copy>(input: I, prefix: &str) -> Result<usize, RespError> {
+    if input.remaining() < 3 {
         return Err(RespError::NotComplete);
     }
 
-    if !buf.starts_with(prefix.as_bytes()) {
-        return Err(RespError::InvalidFrameType(format!(
-            "expect: SimpleString({}), got: {:?}",
-            prefix, buf
-        )));
+    for (i, &want) in prefix.as_bytes().iter().enumerate() {
+        if input.peek(i) != Some(want) {
+            return Err(RespError::InvalidFrameType(format!(
+                "expect: SimpleString({}), got something else",
+                prefix
+            )));
+        }
     }
 
-    let end = find_crlf(buf, 1).ok_or(RespError::NotComplete)?;
+    let end = find_crlf(input, 1).ok_or(RespError::NotComplete)?;
     Ok(end)
 }
 
-fn find_crlf(buf: &[u8], nth: usize) -> Option<usize> {
+fn find_crlf<I: Input + Copy>(input: I, nth: usize) -> Option<usize> {
     let mut count = 0;
-    for i in 0..buf.len() - 1 {
-        if buf[i] == b'\r' && buf[i + 1] == b'\n' {
+    let len = input.remaining();
+    if len < 2 {
+        return None;
+    }
+    for i in 0..len - 1 {
+        if input.peek(i) == Some(b'\r') && input.peek(i + 1) == Some(b'\n') {
             count += 1;
             if count == nth {
                 return Some(i);
@@ -278,15 +471,24 @@ mod tests {
     #[test]
     fn test_calc_array_length() -> Result<()> {
         let buf = b"*2\r\n$3\r\nset\r\n$5\r\nhello\r\n";
-        let (end, len) = parse_length(buf, "*")?;
-        let total_len = calc_total_length(buf, end, len, "*")?;
+        let (end, len) = parse_length(&buf[..], "*")?;
+        let total_len = calc_total_length(buf, end, len, "*", DecodeLimits::default(), 0)?;
         assert_eq!(total_len, buf.len());
 
         let buf = b"*2\r\n$3\r\nset\r\n";
-        let (end, len) = parse_length(buf, "*")?;
-        let ret = calc_total_length(buf, end, len, "*");
+        let (end, len) = parse_length(&buf[..], "*")?;
+        let ret = calc_total_length(buf, end, len, "*", DecodeLimits::default(), 0);
         assert_eq!(ret.unwrap_err(), RespError::NotComplete);
 
         Ok(())
     }
+
+    #[test]
+    fn test_calc_array_length_rejects_excessive_depth() {
+        let buf = b"*1\r\n*1\r\n*1\r\n$1\r\nx\r\n";
+        let (end, len) = parse_length(&buf[..], "*").unwrap();
+        let limits = DecodeLimits::new(512 * 1024 * 1024, 1);
+        let ret = calc_total_length(buf, end, len, "*", limits, 0);
+        assert_eq!(ret.unwrap_err(), RespError::MaxDepthExceeded(2));
+    }
 }